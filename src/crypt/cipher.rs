@@ -0,0 +1,70 @@
+use super::CryptError;
+
+/// A block cipher operated in a chaining mode (e.g. CBC), where each block is
+/// mixed with an initialization vector that is updated in place as blocks are
+/// processed.
+pub trait BlockCipher {
+    /// Size, in bytes, of a single block this cipher consumes/produces.
+    fn block_size(&self) -> usize;
+
+    /// Encrypt one block of `src` into `dst`, updating `iv` for the next block.
+    fn encrypt(&self, iv: &mut [u8], dst: &mut [u8], src: &[u8]) -> Result<(), CryptError>;
+
+    /// Decrypt one block of `src` into `dst`, updating `iv` for the next block.
+    fn decrypt(&self, iv: &mut [u8], dst: &mut [u8], src: &[u8]) -> Result<(), CryptError>;
+}
+
+/// A simple XOR-based cipher run in CBC mode: each plaintext block is XORed
+/// with the running IV and the (repeated) key before becoming the next IV.
+///
+/// As with the rest of `crypt`, this trades cryptographic strength for
+/// simplicity and convenience. Do not use it to protect anything that matters.
+pub struct XorCipher {
+    key: Vec<u8>,
+    block_size: usize,
+}
+
+impl XorCipher {
+    /// Create a cipher with the given `key` and `block_size`. The key is
+    /// cycled to cover blocks larger than the key itself.
+    pub fn new(key: Vec<u8>, block_size: usize) -> Self {
+        XorCipher { key, block_size }
+    }
+
+    fn check_lengths(&self, iv: &[u8], dst: &[u8], src: &[u8]) -> Result<(), CryptError> {
+        if src.len() != self.block_size {
+            return Err(CryptError::InvalidBlockLength(src.len(), self.block_size));
+        }
+        if dst.len() != self.block_size {
+            return Err(CryptError::InvalidBlockLength(dst.len(), self.block_size));
+        }
+        if iv.len() != self.block_size {
+            return Err(CryptError::InvalidBlockLength(iv.len(), self.block_size));
+        }
+        Ok(())
+    }
+}
+
+impl BlockCipher for XorCipher {
+    fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    fn encrypt(&self, iv: &mut [u8], dst: &mut [u8], src: &[u8]) -> Result<(), CryptError> {
+        self.check_lengths(iv, dst, src)?;
+        for i in 0..self.block_size {
+            dst[i] = src[i] ^ iv[i] ^ self.key[i % self.key.len()];
+        }
+        iv.copy_from_slice(dst); // CBC chaining: ciphertext feeds the next IV
+        Ok(())
+    }
+
+    fn decrypt(&self, iv: &mut [u8], dst: &mut [u8], src: &[u8]) -> Result<(), CryptError> {
+        self.check_lengths(iv, dst, src)?;
+        for i in 0..self.block_size {
+            dst[i] = src[i] ^ iv[i] ^ self.key[i % self.key.len()];
+        }
+        iv.copy_from_slice(src); // next IV is this block's ciphertext
+        Ok(())
+    }
+}
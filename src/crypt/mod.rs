@@ -0,0 +1,38 @@
+mod cipher;
+
+pub use cipher::{BlockCipher, XorCipher};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CryptError {
+    #[error("Buffer length ({0}) does not match block size ({1})")]
+    InvalidBlockLength(usize, usize),
+
+    #[error("Data is not a whole number of blocks, or padding is malformed")]
+    BadPadding,
+}
+
+/// Pad `data` up to a multiple of `block_size` using PKCS#7: every added byte
+/// holds the number of padding bytes, and a full extra block is appended if
+/// `data` is already a multiple of `block_size`.
+pub fn pkcs7_pad(data: &[u8], block_size: usize) -> Vec<u8> {
+    let pad_len = block_size - (data.len() % block_size);
+    let mut padded = data.to_vec();
+    padded.extend(std::iter::repeat_n(pad_len as u8, pad_len));
+    padded
+}
+
+/// Reverse [`pkcs7_pad`], validating that the padding bytes are well-formed.
+pub fn pkcs7_unpad(data: &[u8]) -> Result<Vec<u8>, CryptError> {
+    let pad_len = *data.last().ok_or(CryptError::BadPadding)? as usize;
+    if pad_len == 0 || pad_len > data.len() {
+        return Err(CryptError::BadPadding);
+    }
+    if !data[data.len() - pad_len..].iter().all(|&b| b as usize == pad_len) {
+        return Err(CryptError::BadPadding);
+    }
+    Ok(data[..data.len() - pad_len].to_vec())
+}
+
+pub mod prelude {
+    pub use super::{BlockCipher, CryptError, XorCipher};
+}
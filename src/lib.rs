@@ -10,10 +10,16 @@
 //!
 //! - [sten] for steganography
 //! - [crypt] for crytography
+//! - [protected] for scrubbing sensitive bytes from memory
 //!
 //! Do refer to individual module's documentation for more information.
 #![deny(clippy::all)]
 
+/// A small helper for holding onto sensitive bytes for as short a time as
+/// possible: anything wrapped in [`protected::Protected`] is zeroed out the
+/// moment it is dropped.
+pub mod protected;
+
 /// Collection of cryptographic utilities.
 ///
 /// `xtrk` allows simple, and probably not very secure, symmetrically or asymmetrically,
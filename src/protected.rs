@@ -0,0 +1,64 @@
+use std::ops::{Deref, DerefMut};
+
+/// Wraps a byte-backed value and zeroes its backing storage when dropped.
+///
+/// Intended for secrets that only need to live for a short while, such as a
+/// decoded payload from [`crate::sten::Destenable::desten_protected`] or key
+/// material passed into [`crate::crypt`]. The zeroing write is volatile so the
+/// optimizer cannot reason the store away as dead code.
+pub struct Protected<T: AsMut<[u8]>>(T);
+
+impl<T: AsMut<[u8]>> Protected<T> {
+    /// Wrap `inner`, taking ownership of it.
+    pub fn new(inner: T) -> Self {
+        Protected(inner)
+    }
+}
+
+impl<T: AsMut<[u8]>> Deref for Protected<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: AsMut<[u8]>> DerefMut for Protected<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: AsMut<[u8]>> Drop for Protected<T> {
+    fn drop(&mut self) {
+        for byte in self.0.as_mut().iter_mut() {
+            // SAFETY: `byte` is a valid, aligned reference into `self.0` for the
+            // duration of this write.
+            unsafe {
+                std::ptr::write_volatile(byte, 0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protected_derefs_to_inner_value() {
+        let protected = Protected::new(vec![1u8, 2, 3]);
+        assert_eq!(&*protected, &[1u8, 2, 3]);
+    }
+
+    #[test]
+    fn test_protected_zeroes_backing_storage_on_drop() {
+        // Wrap a slice into stack-owned storage so we can inspect the bytes
+        // after `Protected` is dropped without reading freed heap memory.
+        let mut backing = [0xAAu8; 16];
+        {
+            let _protected = Protected::new(&mut backing[..]);
+        }
+        assert!(backing.iter().all(|&b| b == 0));
+    }
+}
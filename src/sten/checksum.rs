@@ -0,0 +1,180 @@
+//! Pluggable integrity checksums for the stego frame trailer.
+
+/// An integrity checksum that digests data into a fixed-size tag.
+///
+/// `LEN` is carried as a const generic so implementations can return a plain
+/// `[u8; LEN]` without any heap allocation, and so [`Stenable::sten_with`](super::Stenable::sten_with) /
+/// [`Destenable::desten_with`](super::Destenable::desten_with) can size the
+/// trailer at compile time.
+pub trait Checksum<const LEN: usize> {
+    /// A one-byte tag identifying this algorithm inside the stego frame, so a
+    /// frame is at least able to detect it was verified with the wrong one.
+    const TAG: u8;
+
+    /// Digest `data` into a `LEN`-byte tag.
+    fn digest(&self, data: &[u8]) -> [u8; LEN];
+}
+
+/// The crate's original CRC-32 checksum, kept as the default: cheap, and good
+/// for catching accidental corruption, but trivial to forge deliberately.
+pub struct Crc32;
+
+impl Checksum<4> for Crc32 {
+    const TAG: u8 = 0;
+
+    fn digest(&self, data: &[u8]) -> [u8; 4] {
+        super::get_crc(0, data).to_le_bytes()
+    }
+}
+
+/// A keyed or unkeyed BLAKE2b digest, truncated to `LEN` bytes. Unlike
+/// [`Crc32`], forging a matching tag without knowing the key (if any) is
+/// computationally infeasible, which makes it suitable for tamper evidence
+/// rather than just corruption detection.
+pub struct Blake2b<const LEN: usize> {
+    key: Vec<u8>,
+}
+
+impl<const LEN: usize> Blake2b<LEN> {
+    /// An unkeyed BLAKE2b instance.
+    pub fn new() -> Self {
+        Blake2b { key: Vec::new() }
+    }
+
+    /// A keyed BLAKE2b instance. `key` must be at most 64 bytes.
+    pub fn with_key(key: Vec<u8>) -> Self {
+        Blake2b { key }
+    }
+}
+
+impl<const LEN: usize> Default for Blake2b<LEN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const LEN: usize> Checksum<LEN> for Blake2b<LEN> {
+    const TAG: u8 = 1;
+
+    fn digest(&self, data: &[u8]) -> [u8; LEN] {
+        let full = blake2b(data, &self.key, LEN);
+        let mut out = [0u8; LEN];
+        out.copy_from_slice(&full);
+        out
+    }
+}
+
+const BLAKE2B_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const BLAKE2B_SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+#[allow(clippy::too_many_arguments)]
+fn blake2b_mix(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+fn blake2b_compress(h: &mut [u64; 8], block: &[u8; 128], byte_count: u128, is_last: bool) {
+    let mut m = [0u64; 16];
+    for (i, word) in m.iter_mut().enumerate() {
+        *word = u64::from_le_bytes(block[i * 8..i * 8 + 8].try_into().unwrap());
+    }
+
+    let mut v = [0u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..16].copy_from_slice(&BLAKE2B_IV);
+
+    v[12] ^= byte_count as u64;
+    v[13] ^= (byte_count >> 64) as u64;
+    if is_last {
+        v[14] = !v[14];
+    }
+
+    for sigma in BLAKE2B_SIGMA {
+        blake2b_mix(&mut v, 0, 4, 8, 12, m[sigma[0]], m[sigma[1]]);
+        blake2b_mix(&mut v, 1, 5, 9, 13, m[sigma[2]], m[sigma[3]]);
+        blake2b_mix(&mut v, 2, 6, 10, 14, m[sigma[4]], m[sigma[5]]);
+        blake2b_mix(&mut v, 3, 7, 11, 15, m[sigma[6]], m[sigma[7]]);
+        blake2b_mix(&mut v, 0, 5, 10, 15, m[sigma[8]], m[sigma[9]]);
+        blake2b_mix(&mut v, 1, 6, 11, 12, m[sigma[10]], m[sigma[11]]);
+        blake2b_mix(&mut v, 2, 7, 8, 13, m[sigma[12]], m[sigma[13]]);
+        blake2b_mix(&mut v, 3, 4, 9, 14, m[sigma[14]], m[sigma[15]]);
+    }
+
+    for i in 0..8 {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Compute a BLAKE2b digest of `data`, optionally keyed, truncated to
+/// `out_len` bytes (at most 64).
+fn blake2b(data: &[u8], key: &[u8], out_len: usize) -> Vec<u8> {
+    let mut h = BLAKE2B_IV;
+    h[0] ^= 0x01010000 ^ ((key.len() as u64) << 8) ^ out_len as u64;
+
+    let mut blocks: Vec<[u8; 128]> = Vec::new();
+    if !key.is_empty() {
+        let mut block = [0u8; 128];
+        block[..key.len()].copy_from_slice(key);
+        blocks.push(block);
+    }
+    if data.is_empty() {
+        if blocks.is_empty() {
+            blocks.push([0u8; 128]);
+        }
+    } else {
+        for chunk in data.chunks(128) {
+            let mut block = [0u8; 128];
+            block[..chunk.len()].copy_from_slice(chunk);
+            blocks.push(block);
+        }
+    }
+
+    let key_prefix_len: u128 = if key.is_empty() { 0 } else { 128 };
+    let last = blocks.len() - 1;
+    let mut byte_count: u128 = 0;
+    for (i, block) in blocks.iter().enumerate() {
+        let is_last = i == last;
+        byte_count = if is_last {
+            key_prefix_len + data.len() as u128
+        } else {
+            byte_count + 128
+        };
+        blake2b_compress(&mut h, block, byte_count, is_last);
+    }
+
+    let mut out = Vec::with_capacity(out_len);
+    for word in h {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    out.truncate(out_len);
+    out
+}
@@ -1,10 +1,29 @@
+mod checksum;
 mod image_io;
+mod stream;
+
+pub use checksum::{Blake2b, Checksum, Crc32};
+pub use stream::{StenReader, StenWriter};
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::protected::Protected;
+
+#[cfg(feature = "crypt")]
+use crate::crypt::{pkcs7_pad, pkcs7_unpad, BlockCipher};
+#[cfg(feature = "crypt")]
+use rand::Rng;
 
 #[derive(Debug, thiserror::Error)]
 pub enum StenError {
     #[error("{0}")]
     ImageIoError(#[from] image_io::ImageIoError),
 
+    #[cfg(feature = "crypt")]
+    #[error("{0}")]
+    CryptoError(#[from] crate::crypt::CryptError),
+
     #[error("Payload too large for container. Payload size: {0}, maximum size: {1}")]
     PayloadTooLarge(usize, usize),
 
@@ -31,6 +50,168 @@ fn get_crc(crc: u32, data: &[u8]) -> u32 {
     !crc
 }
 
+/// Compare two equal-length byte slices in constant time, so the comparison
+/// does not short-circuit on the first mismatching byte. Returns `false` if
+/// the slices differ in length.
+fn secure_cmp(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+/// Maximum number of 7-bit groups a varint header may span. Ten groups cover
+/// the 64 bits of a `u64` (`ceil(64 / 7) == 10`), so anything longer is malformed.
+const MAX_VARINT_GROUPS: usize = 10;
+
+/// Encode `value` as an unsigned LEB128 varint: 7 bits per byte, high bit set
+/// on every byte except the last to signal continuation.
+fn encode_varint(mut value: u64) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+        if value == 0 {
+            return bytes;
+        }
+    }
+}
+
+/// Decode a varint header from the front of a stego container, where each
+/// container byte holds one payload bit in its LSB. Returns the decoded value
+/// together with the number of container bytes (bits) consumed.
+fn decode_varint_header(container: &[u8]) -> Result<(u64, usize), StenError> {
+    let mut value: u64 = 0;
+    let mut container_pos = 0;
+
+    for group in 0..MAX_VARINT_GROUPS {
+        if container_pos + 8 > container.len() {
+            return Err(StenError::BadPayload);
+        }
+
+        let mut byte = 0u8;
+        for bit_pos in 0..8 {
+            let bit = container[container_pos + bit_pos] & 1;
+            byte |= bit << bit_pos;
+        }
+        container_pos += 8;
+
+        value |= ((byte & 0x7F) as u64) << (7 * group);
+        if byte & 0x80 == 0 {
+            return Ok((value, container_pos));
+        }
+    }
+
+    Err(StenError::BadPayload)
+}
+
+/// Decode a varint from the front of a plain byte buffer (as opposed to
+/// [`decode_varint_header`], which reads one bit per container byte). Used to
+/// read the length prefix of a nested sub-item inside a composite payload.
+/// Returns the decoded value together with the number of bytes consumed, or
+/// `None` if the varint never terminates within `data`.
+fn decode_varint_bytes(data: &[u8]) -> Option<(u64, usize)> {
+    let mut value: u64 = 0;
+
+    for (group, &byte) in data.iter().take(MAX_VARINT_GROUPS).enumerate() {
+        value |= ((byte & 0x7F) as u64) << (7 * group);
+        if byte & 0x80 == 0 {
+            return Some((value, group + 1));
+        }
+    }
+
+    None
+}
+
+/// Write a varint-prefixed, already-framed payload into `container`'s LSBs.
+fn embed_payload<C: Container>(container: &mut C, framed_payload: Vec<u8>) -> Result<(), StenError> {
+    let container = container.as_mut_bytes();
+
+    let max_size = container.len() / 8; // in bytes
+    let framed_payload_size = framed_payload.len(); // in bytes
+    if framed_payload_size > max_size {
+        return Err(StenError::PayloadTooLarge(framed_payload_size, max_size));
+    }
+
+    let mut container_byte_pos = 0; // to keep track of the container byte position
+                                     // Iterate over byte of payload
+    for payload_byte in framed_payload {
+        // Iterate over bit position of payload byte
+        for payload_bit_pos in 0..8 {
+            // Get payload bit
+            let payload_bit = (payload_byte >> payload_bit_pos) & 1;
+
+            // Unset the container bit
+            container[container_byte_pos] &= 0xFE;
+
+            // Set the container bit at the position with the payload bit
+            container[container_byte_pos] |= payload_bit;
+
+            // Move to the next container byte
+            container_byte_pos += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a varint-prefixed framed payload (without interpreting its tail) out
+/// of `container`'s LSBs.
+fn extract_payload<C: Container>(container: &C) -> Result<Vec<u8>, StenError> {
+    let container = container.as_bytes();
+
+    // Validate container minimum size (at least one varint group)
+    if container.len() < 8 {
+        return Err(StenError::BadPayload);
+    }
+
+    // Extract payload size from the varint header
+    let (payload_size, header_bits) = decode_varint_header(container)?;
+    let payload_size = payload_size as usize;
+
+    // Each container byte stores 1 bit, so guard the multiplication (and the
+    // subsequent addition) against a malformed header claiming a
+    // near-`u64::MAX` payload size.
+    let bytes_to_read = payload_size.checked_mul(8).ok_or(StenError::BadPayload)?;
+    let total_bits = header_bits.checked_add(bytes_to_read).ok_or(StenError::BadPayload)?;
+    if total_bits > container.len() {
+        return Err(StenError::BadPayload);
+    }
+    let mut payload_byte = 0u8; // to build up the payload byte from each bit read
+    let mut bit_read = 0; // to set bit in payload byte and to know when to move on to the next byte
+    let mut framed_payload = Vec::with_capacity(payload_size);
+
+    for container_byte in container[header_bits..total_bits].iter() {
+        // Read the LSB of the container byte
+        let container_bit = container_byte & 1;
+
+        // Set the payload byte bit
+        payload_byte |= container_bit << bit_read;
+
+        // Move to the next bit
+        bit_read += 1;
+
+        // If we have read 8 bits, then we have a full payload byte,
+        // then insert it to the payload
+        if bit_read == 8 {
+            framed_payload.push(payload_byte);
+            payload_byte = 0;
+            bit_read = 0;
+        }
+    }
+
+    Ok(framed_payload)
+}
+
 pub trait Container {
     fn as_mut_bytes(&mut self) -> &mut [u8];
     fn as_bytes(&self) -> &[u8];
@@ -51,48 +232,62 @@ pub trait Stenable: Sized {
     /// Get raw bytes of the object.
     fn get_raw_bytes(self) -> Vec<u8>;
 
-    /// Perform stenographic operation. Default implementation is provided.
+    /// Perform stenographic operation, checksummed with [`Crc32`]. Default
+    /// implementation is provided.
     fn sten<C: Container>(self, container: &mut C) -> Result<(), StenError> {
-        let mut payload = self.get_raw_bytes();
-
-        // Calculate checksum and build payload
-        let crc = get_crc(0, &payload).to_le_bytes(); // convert checksum to little endian bytes
-        payload.extend_from_slice(&crc);
-
-        // Prepend payload size to payload
-        let container = container.as_mut_bytes();
-        let payload_size = payload.len() as u32;
-        let payload_size = payload_size.to_le_bytes();
-        let mut new_payload = payload_size.to_vec();
-        new_payload.extend_from_slice(&payload);
-
-        // Perform size check
-        let max_size = container.len() / 8; // in bytes
-        let new_payload_size = new_payload.len(); // in bytes
-        if new_payload_size > max_size {
-            return Err(StenError::PayloadTooLarge(new_payload_size, max_size));
-        }
-
-        let mut container_byte_pos = 0; // to keep track of the container byte position
-                                        // Iterate over byte of payload
-        for payload_byte in new_payload {
-            // Iterate over bit position of payload byte
-            for payload_bit_pos in 0..8 {
-                // Get payload bit
-                let payload_bit = (payload_byte >> payload_bit_pos) & 1;
-
-                // Unset the container bit
-                container[container_byte_pos] &= 0xFE;
-
-                // Set the container bit at the position with the payload bit
-                container[container_byte_pos] |= payload_bit;
-
-                // Move to the next container byte
-                container_byte_pos += 1;
-            }
+        self.sten_with(container, &Crc32)
+    }
+
+    /// Like [`sten`](Stenable::sten), but with a pluggable [`Checksum`]
+    /// instead of the hardcoded CRC-32, for when tamper evidence matters more
+    /// than a cheap 4-byte trailer.
+    fn sten_with<C: Container, const LEN: usize, S: Checksum<LEN>>(
+        self,
+        container: &mut C,
+        checksum: &S,
+    ) -> Result<(), StenError> {
+        let payload = self.get_raw_bytes();
+
+        // Tag the algorithm used, then append its digest of the payload.
+        let digest = checksum.digest(&payload);
+        let mut framed_payload = payload;
+        framed_payload.push(S::TAG);
+        framed_payload.extend_from_slice(&digest);
+
+        // Prepend a varint-encoded payload size, so small payloads only pay
+        // for as many header bytes as they actually need.
+        let mut new_payload = encode_varint(framed_payload.len() as u64);
+        new_payload.extend_from_slice(&framed_payload);
+
+        embed_payload(container, new_payload)
+    }
+
+    /// Encrypt the payload with `cipher` before hiding it, so a detected
+    /// stego container does not also hand over the plaintext. A random IV is
+    /// generated and prepended to the ciphertext, and the existing checksum
+    /// trailer is computed over the ciphertext rather than the plaintext.
+    #[cfg(feature = "crypt")]
+    fn sten_encrypted<C: Container, B: BlockCipher>(
+        self,
+        container: &mut C,
+        cipher: &B,
+    ) -> Result<(), StenError> {
+        let block_size = cipher.block_size();
+        let mut iv = vec![0u8; block_size];
+        rand::thread_rng().fill(iv.as_mut_slice());
+
+        let padded = pkcs7_pad(&self.get_raw_bytes(), block_size);
+        let mut running_iv = iv.clone();
+        let mut ciphertext = Vec::with_capacity(padded.len());
+        for block in padded.chunks(block_size) {
+            let mut out = vec![0u8; block_size];
+            cipher.encrypt(&mut running_iv, &mut out, block)?;
+            ciphertext.extend_from_slice(&out);
         }
 
-        Ok(())
+        let mut framed = iv;
+        framed.extend_from_slice(&ciphertext);
+        framed.sten(container)
     }
 }
 
@@ -101,64 +296,81 @@ pub trait Destenable: Sized {
     /// Convert raw bytes to object.
     fn from_raw_bytes(data: Vec<u8>) -> Option<Self>;
 
-    /// Reverse stenographic operation. Default implementation is provided.
+    /// Reverse stenographic operation, checksummed with [`Crc32`]. Default
+    /// implementation is provided.
     fn desten<C: Container>(container: &C) -> Result<Self, StenError> {
-        let container = container.as_bytes();
-
-        // Validate container minimum size
-        if container.len() < 32 {
+        Self::desten_with(container, &Crc32)
+    }
+
+    /// Reverse [`Stenable::sten_with`]: validate the tag and digest, then
+    /// parse the payload. Verification reads the tag first so a frame
+    /// checksummed with a different algorithm is rejected instead of
+    /// silently compared against the wrong digest.
+    fn desten_with<C: Container, const LEN: usize, S: Checksum<LEN>>(
+        container: &C,
+        checksum: &S,
+    ) -> Result<Self, StenError> {
+        let framed_payload = extract_payload(container)?;
+
+        // Extract tag, digest and actual payload
+        let tail_len = 1 + LEN;
+        if framed_payload.len() < tail_len {
             return Err(StenError::BadPayload);
         }
+        let split = framed_payload.len() - tail_len;
+        let payload = framed_payload[..split].to_vec();
+        let tag = framed_payload[split];
+        let expected_digest = &framed_payload[split + 1..];
 
-        // Extract payload size in bytes (first 4 bytes)
-        let payload_size: usize = container[..32] // last 32 payload bits = 4 payload bytes
-            .iter()
-            .map(|b| b & 1) // get the last bit of each byte
-            .enumerate() // pair each bit with its position
-            .map(|(i, b)| (b as usize) << i) // shift the bit to its position
-            .sum(); // add all the bits together
-
-        let bytes_to_read = payload_size * 8; // each container byte store 1 bit
-        let mut payload_byte = 0u8; // to build up the payload byte from each bit read
-        let mut bit_read = 0; // to set bit in payload byte and to know when to move on to the next byte
-        let mut payload_with_checksum = Vec::with_capacity(payload_size);
-
-        for container_byte in container[32..32 + bytes_to_read].iter() {
-            // Read the LSB of the container byte
-            let container_bit = container_byte & 1;
-
-            // Set the payload byte bit
-            payload_byte |= container_bit << bit_read;
-
-            // Move to the next bit
-            bit_read += 1;
-
-            // If we have read 8 bits, then we have a full payload byte,
-            // then insert it to the payload
-            if bit_read == 8 {
-                payload_with_checksum.push(payload_byte);
-                payload_byte = 0;
-                bit_read = 0;
-            }
+        if tag != S::TAG {
+            return Err(StenError::BadPayload);
         }
 
-        // Extract checksum and actual payload
-        let payload = payload_with_checksum[..payload_with_checksum.len() - 4].to_vec();
-        let expected = u32::from_le_bytes(
-            payload_with_checksum[payload_with_checksum.len() - 4..]
-                .try_into()
-                .unwrap(), // can unwrap since payload size is at least 4
-        );
-
-        // Calculate and compare actual checksum
-        let actual = get_crc(0, &payload);
-        if expected != actual {
+        // Calculate and compare actual digest in constant time
+        let actual_digest = checksum.digest(&payload);
+        if !secure_cmp(expected_digest, &actual_digest) {
             return Err(StenError::FailedChecksum);
         }
 
         // Decode payload
-        let p = Self::from_raw_bytes(payload).ok_or(StenError::FailedParsing)?;
-        Ok(p)
+        Self::from_raw_bytes(payload).ok_or(StenError::FailedParsing)
+    }
+
+    /// Reverse [`Stenable::sten_encrypted`]: extract the IV-prefixed
+    /// ciphertext, decrypt it block by block, then parse the plaintext.
+    #[cfg(feature = "crypt")]
+    fn desten_encrypted<C: Container, B: BlockCipher>(
+        container: &C,
+        cipher: &B,
+    ) -> Result<Self, StenError> {
+        let framed = Vec::<u8>::desten(container)?;
+        let block_size = cipher.block_size();
+
+        if framed.len() < block_size || !(framed.len() - block_size).is_multiple_of(block_size) {
+            return Err(StenError::BadPayload);
+        }
+        let (iv, ciphertext) = framed.split_at(block_size);
+        let mut running_iv = iv.to_vec();
+
+        let mut padded = Vec::with_capacity(ciphertext.len());
+        for block in ciphertext.chunks(block_size) {
+            let mut out = vec![0u8; block_size];
+            cipher.decrypt(&mut running_iv, &mut out, block)?;
+            padded.extend_from_slice(&out);
+        }
+
+        let plaintext = pkcs7_unpad(&padded)?;
+        Self::from_raw_bytes(plaintext).ok_or(StenError::FailedParsing)
+    }
+
+    /// Like [`desten`](Destenable::desten), but wraps the result in
+    /// [`Protected`] so the recovered secret is zeroed out of memory as soon
+    /// as the caller is done with it.
+    fn desten_protected<C: Container>(container: &C) -> Result<Protected<Self>, StenError>
+    where
+        Self: AsMut<[u8]>,
+    {
+        Ok(Protected::new(Self::desten(container)?))
     }
 }
 
@@ -333,26 +545,187 @@ impl Destenable for f64 {
     }
 }
 
+/// Discriminant byte prepended to a `Result`'s raw bytes, so decoding never
+/// has to guess whether the payload belongs to the `Ok` or `Err` variant.
+const RESULT_TAG_OK: u8 = 0;
+const RESULT_TAG_ERR: u8 = 1;
+
 impl<T: Stenable, E: Stenable> Stenable for Result<T, E> {
     fn get_raw_bytes(self) -> Vec<u8> {
-        match self {
-            Ok(t) => t.get_raw_bytes(),
-            Err(e) => e.get_raw_bytes(),
-        }
+        let (tag, mut bytes) = match self {
+            Ok(t) => (RESULT_TAG_OK, t.get_raw_bytes()),
+            Err(e) => (RESULT_TAG_ERR, e.get_raw_bytes()),
+        };
+        let mut out = vec![tag];
+        out.append(&mut bytes);
+        out
     }
 }
 
 impl<T: Destenable, E: Destenable> Destenable for Result<T, E> {
     fn from_raw_bytes(data: Vec<u8>) -> Option<Self> {
-        T::from_raw_bytes(data.clone())
-            .map(Ok)
-            .or_else(|| E::from_raw_bytes(data).map(Err))
+        let (&tag, rest) = data.split_first()?;
+        match tag {
+            RESULT_TAG_OK => T::from_raw_bytes(rest.to_vec()).map(Ok),
+            RESULT_TAG_ERR => E::from_raw_bytes(rest.to_vec()).map(Err),
+            _ => None,
+        }
+    }
+}
+
+/// Encode a sub-item as `varint(len) || bytes`, so composite types (tuples,
+/// fixed arrays, `List`, `HashMap`) can concatenate fields unambiguously.
+fn encode_item(bytes: Vec<u8>) -> Vec<u8> {
+    let mut out = encode_varint(bytes.len() as u64);
+    out.extend_from_slice(&bytes);
+    out
+}
+
+/// Read one length-prefixed sub-item off the front of `rest`, returning its
+/// bytes and the remaining, unread tail.
+fn take_item(rest: &[u8]) -> Option<(Vec<u8>, &[u8])> {
+    let (len, consumed) = decode_varint_bytes(rest)?;
+    let len = len as usize;
+    let end = consumed.checked_add(len)?;
+    if end > rest.len() {
+        return None;
+    }
+    Some((rest[consumed..end].to_vec(), &rest[end..]))
+}
+
+macro_rules! impl_stenable_tuple {
+    ($($T:ident),+) => {
+        impl<$($T: Stenable),+> Stenable for ($($T,)+) {
+            #[allow(non_snake_case)]
+            fn get_raw_bytes(self) -> Vec<u8> {
+                let ($($T,)+) = self;
+                let mut out = Vec::new();
+                $(out.extend(encode_item($T.get_raw_bytes()));)+
+                out
+            }
+        }
+
+        impl<$($T: Destenable),+> Destenable for ($($T,)+) {
+            #[allow(non_snake_case, unused_assignments)]
+            fn from_raw_bytes(data: Vec<u8>) -> Option<Self> {
+                let mut rest = data.as_slice();
+                $(
+                    let (field_bytes, remainder) = take_item(rest)?;
+                    let $T = $T::from_raw_bytes(field_bytes)?;
+                    rest = remainder;
+                )+
+                Some(($($T,)+))
+            }
+        }
+    };
+}
+
+impl_stenable_tuple!(A);
+impl_stenable_tuple!(A, B);
+impl_stenable_tuple!(A, B, C);
+impl_stenable_tuple!(A, B, C, D);
+impl_stenable_tuple!(A, B, C, D, E);
+impl_stenable_tuple!(A, B, C, D, E, F);
+impl_stenable_tuple!(A, B, C, D, E, F, G);
+impl_stenable_tuple!(A, B, C, D, E, F, G, H);
+impl_stenable_tuple!(A, B, C, D, E, F, G, H, I);
+impl_stenable_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_stenable_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_stenable_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+impl<T: Stenable, const N: usize> Stenable for [T; N] {
+    fn get_raw_bytes(self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for item in self {
+            out.extend(encode_item(item.get_raw_bytes()));
+        }
+        out
+    }
+}
+
+impl<T: Destenable, const N: usize> Destenable for [T; N] {
+    fn from_raw_bytes(data: Vec<u8>) -> Option<Self> {
+        let mut rest = data.as_slice();
+        let mut items = Vec::with_capacity(N);
+        for _ in 0..N {
+            let (field_bytes, remainder) = take_item(rest)?;
+            items.push(T::from_raw_bytes(field_bytes)?);
+            rest = remainder;
+        }
+        if !rest.is_empty() {
+            return None;
+        }
+        items.try_into().ok()
+    }
+}
+
+/// A length-prefixed list of [`Stenable`] items.
+///
+/// This is a deliberate substitute for a blanket `impl<T: Stenable> Stenable
+/// for Vec<T>`, not a drop-in: `Vec<u8>` above keeps its raw, un-framed
+/// passthrough encoding (it is the frame's own internal payload
+/// representation), so a generic `Vec<T>` impl would conflict with it under
+/// Rust's coherence rules. Callers with a `Vec<T>` of anything other than
+/// `u8` need to wrap it as `List(vec)` explicitly; `List` cannot be used
+/// where a `Vec<T>` is expected.
+pub struct List<T>(pub Vec<T>);
+
+impl<T: Stenable> Stenable for List<T> {
+    fn get_raw_bytes(self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for item in self.0 {
+            out.extend(encode_item(item.get_raw_bytes()));
+        }
+        out
+    }
+}
+
+impl<T: Destenable> Destenable for List<T> {
+    fn from_raw_bytes(data: Vec<u8>) -> Option<Self> {
+        let mut rest = data.as_slice();
+        let mut items = Vec::new();
+        while !rest.is_empty() {
+            let (field_bytes, remainder) = take_item(rest)?;
+            items.push(T::from_raw_bytes(field_bytes)?);
+            rest = remainder;
+        }
+        Some(List(items))
+    }
+}
+
+impl<K: Stenable, V: Stenable> Stenable for HashMap<K, V> {
+    fn get_raw_bytes(self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (k, v) in self {
+            out.extend(encode_item(k.get_raw_bytes()));
+            out.extend(encode_item(v.get_raw_bytes()));
+        }
+        out
+    }
+}
+
+impl<K: Destenable + Eq + Hash, V: Destenable> Destenable for HashMap<K, V> {
+    fn from_raw_bytes(data: Vec<u8>) -> Option<Self> {
+        let mut rest = data.as_slice();
+        let mut map = HashMap::new();
+        while !rest.is_empty() {
+            let (key_bytes, remainder) = take_item(rest)?;
+            let key = K::from_raw_bytes(key_bytes)?;
+            let (value_bytes, remainder) = take_item(remainder)?;
+            let value = V::from_raw_bytes(value_bytes)?;
+            map.insert(key, value);
+            rest = remainder;
+        }
+        Some(map)
     }
 }
 
 pub mod prelude {
-    pub use super::image_io::Image;
-    pub use super::{Container, Destenable, StenError, Stenable};
+    pub use super::image_io::StenImage;
+    pub use super::{
+        Blake2b, Checksum, Container, Crc32, Destenable, List, StenError, StenReader, StenWriter,
+        Stenable,
+    };
 }
 
 #[cfg(test)]
@@ -385,4 +758,156 @@ mod tests {
     make_test_sten!(test_sten_i64, 256, 0x1234567890ABCDEFi64, i64);
     make_test_sten!(test_sten_f32, 256, 0.12345678f32, f32);
     make_test_sten!(test_sten_f64, 256, 0.1234567890123456f64, f64);
+
+    #[test]
+    fn test_sten_small_payload_uses_compact_header() {
+        // A 1-byte payload needs a 1-byte varint header, so the whole frame
+        // (1 header byte + 1 payload byte + 1 tag byte + 4 checksum bytes)
+        // should fit in a container far smaller than the old fixed 4-byte
+        // header allowed.
+        let mut container = vec![13; 64];
+        vec![42u8].sten(&mut container).unwrap();
+        let decoded = VecByte::desten(&container).unwrap();
+        assert_eq!(decoded, vec![42u8]);
+    }
+
+    #[test]
+    fn test_desten_rejects_unterminated_varint() {
+        // Every container byte has its LSB set, so the varint header never
+        // sees a byte with a clear continuation bit before the container ends.
+        let container = vec![0xFFu8; 256];
+        let err = VecByte::desten(&container).unwrap_err();
+        assert!(matches!(err, StenError::BadPayload));
+    }
+
+    #[test]
+    fn test_desten_rejects_huge_payload_size_without_overflowing() {
+        // A header claiming a near-u64::MAX payload size must be rejected as
+        // malformed rather than overflowing the `* 8` bit-count conversion.
+        let header = encode_varint(u64::MAX);
+        let mut container = vec![0u8; header.len() * 8];
+        for (byte_idx, byte) in header.iter().enumerate() {
+            for bit_pos in 0..8 {
+                container[byte_idx * 8 + bit_pos] = (byte >> bit_pos) & 1;
+            }
+        }
+
+        let err = VecByte::desten(&container).unwrap_err();
+        assert!(matches!(err, StenError::BadPayload));
+    }
+
+    #[cfg(feature = "crypt")]
+    #[test]
+    fn test_sten_encrypted_roundtrip() {
+        use crate::crypt::XorCipher;
+
+        let mut container = vec![13; 2048];
+        let cipher = XorCipher::new(vec![1, 2, 3, 4], 8);
+        let message = String::from("Hello, encrypted Sten");
+
+        message.clone().sten_encrypted(&mut container, &cipher).unwrap();
+        let decoded = String::desten_encrypted(&container, &cipher).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_secure_cmp() {
+        assert!(secure_cmp(&[1, 2, 3], &[1, 2, 3]));
+        assert!(!secure_cmp(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!secure_cmp(&[1, 2, 3], &[1, 2]));
+    }
+
+    #[test]
+    fn test_desten_protected_roundtrip() {
+        let mut container = vec![13; 256];
+        let payload = vec![1u8, 3, 5, 7, 9];
+        payload.clone().sten(&mut container).unwrap();
+
+        let protected = VecByte::desten_protected(&container).unwrap();
+        assert_eq!(&*protected, &payload);
+    }
+
+    #[test]
+    fn test_sten_with_blake2b() {
+        let mut container = vec![13; 4096];
+        let checksum: Blake2b<32> = Blake2b::new();
+        let message = String::from("Hello, tamper-evident Sten");
+
+        message.clone().sten_with(&mut container, &checksum).unwrap();
+        let decoded = String::desten_with(&container, &checksum).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_desten_with_rejects_mismatched_algorithm() {
+        let mut container = vec![13; 256];
+        vec![1u8, 2, 3].sten(&mut container).unwrap(); // framed with Crc32
+
+        // Same trailer length as Crc32 (4 bytes), so this exercises the tag
+        // mismatch rather than a length mismatch.
+        let checksum: Blake2b<4> = Blake2b::new();
+        let err = VecByte::desten_with(&container, &checksum).unwrap_err();
+        assert!(matches!(err, StenError::BadPayload));
+    }
+
+    #[test]
+    fn test_sten_tuple_roundtrip() {
+        let mut container = vec![13; 4096];
+        let payload = (String::from("hi"), 42u32, String::from("bye"));
+
+        payload.clone().sten(&mut container).unwrap();
+        let decoded = <(String, u32, String)>::desten(&container).unwrap();
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn test_sten_fixed_array_roundtrip() {
+        let mut container = vec![13; 4096];
+        let payload = [String::from("a"), String::from("bb"), String::from("ccc")];
+
+        payload.clone().sten(&mut container).unwrap();
+        let decoded = <[String; 3]>::desten(&container).unwrap();
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn test_sten_list_roundtrip() {
+        let mut container = vec![13; 4096];
+        List(vec![1u32, 2, 3, 4]).sten(&mut container).unwrap();
+        let decoded = List::<u32>::desten(&container).unwrap();
+        assert_eq!(decoded.0, vec![1u32, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_take_item_rejects_huge_length_without_overflowing() {
+        // A malformed sub-item length prefix claiming a near-u64::MAX length
+        // must be rejected instead of overflowing `consumed + len`.
+        let data = encode_varint(u64::MAX);
+        assert!(take_item(&data).is_none());
+    }
+
+    #[test]
+    fn test_sten_hashmap_roundtrip() {
+        let mut container = vec![13; 4096];
+        let mut payload = HashMap::new();
+        payload.insert(String::from("a"), 1u32);
+        payload.insert(String::from("b"), 2u32);
+
+        payload.clone().sten(&mut container).unwrap();
+        let decoded = HashMap::<String, u32>::desten(&container).unwrap();
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn test_sten_result_roundtrip() {
+        let mut container = vec![13; 256];
+        let ok: Result<u32, String> = Ok(7);
+        ok.sten(&mut container).unwrap();
+        assert_eq!(Result::<u32, String>::desten(&container).unwrap(), Ok(7));
+
+        let mut container = vec![13; 256];
+        let err: Result<u32, String> = Err(String::from("nope"));
+        err.clone().sten(&mut container).unwrap();
+        assert_eq!(Result::<u32, String>::desten(&container).unwrap(), err);
+    }
 }
@@ -0,0 +1,292 @@
+//! Streaming, `Read`/`Write`-based steganography for payloads too large to
+//! comfortably materialize in memory. The container itself is still accessed
+//! as one in-memory byte slice through [`Container`]; only the payload is
+//! streamed.
+//!
+//! [`StenReader`] and [`StenWriter`] cover the same CRC-32-checksummed frame
+//! as [`Stenable::sten`](super::Stenable::sten) / [`Destenable::desten`](super::Destenable::desten),
+//! but work a block at a time instead of walking the whole container up
+//! front, so they compose with other `Read`/`Write` pipelines (files, pipes,
+//! network sockets).
+
+use std::io::{self, Read, Write};
+
+use super::checksum::{Checksum, Crc32};
+use super::{decode_varint_header, encode_varint, get_crc, secure_cmp, Container, StenError};
+
+/// Number of payload bytes [`StenReader`] buffers internally between container
+/// reads, so a caller doing many small `read` calls isn't walking the
+/// container one byte at a time. [`StenWriter`] does not buffer: each `write`
+/// is embedded into the container as it arrives.
+const STREAM_BLOCK_SIZE: usize = 4096;
+
+/// Tag byte + CRC-32 digest trailing every frame this module reads or writes.
+const TRAILER_LEN: usize = 1 + 4;
+
+fn io_err(err: StenError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Pull `count` payload bytes (one bit per container byte, LSB first, so
+/// `count * 8` container bytes) starting at bit offset `pos`.
+fn extract_bits(container: &[u8], pos: usize, count: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(count);
+    let mut byte = 0u8;
+    let mut bit_read = 0;
+    for container_byte in &container[pos..pos + count * 8] {
+        byte |= (container_byte & 1) << bit_read;
+        bit_read += 1;
+        if bit_read == 8 {
+            out.push(byte);
+            byte = 0;
+            bit_read = 0;
+        }
+    }
+    out
+}
+
+/// Write `data`'s bits into `container`'s LSBs, one bit per container byte,
+/// starting at bit offset `pos`.
+fn embed_bits(container: &mut [u8], pos: usize, data: &[u8]) {
+    let mut container_pos = pos;
+    for byte in data {
+        for bit_pos in 0..8 {
+            let bit = (byte >> bit_pos) & 1;
+            container[container_pos] &= 0xFE;
+            container[container_pos] |= bit;
+            container_pos += 1;
+        }
+    }
+}
+
+/// Incrementally reads a CRC-32-checksummed stego frame out of a container,
+/// yielding the raw payload bytes through [`Read`] without ever holding the
+/// whole payload in memory at once. The container is borrowed whole as an
+/// in-memory byte slice.
+pub struct StenReader<'a> {
+    container: &'a [u8],
+    cursor_bits: usize,
+    payload_remaining: usize,
+    crc: u32,
+    buf: Vec<u8>,
+    buf_pos: usize,
+    trailer_checked: bool,
+}
+
+impl<'a> StenReader<'a> {
+    /// Parse `container`'s varint length header and prepare to stream the
+    /// payload it describes. Fails immediately if the header is malformed or
+    /// claims more bytes than the container can hold.
+    pub fn new<C: Container>(container: &'a C) -> Result<Self, StenError> {
+        let container = container.as_bytes();
+        if container.len() < 8 {
+            return Err(StenError::BadPayload);
+        }
+
+        let (framed_len, header_bits) = decode_varint_header(container)?;
+        let framed_len = framed_len as usize;
+        if framed_len < TRAILER_LEN {
+            return Err(StenError::BadPayload);
+        }
+
+        // Guard the bit-count math against a malformed header claiming a
+        // near-`u64::MAX` framed length.
+        let payload_bits = framed_len.checked_mul(8).ok_or(StenError::BadPayload)?;
+        let total_bits = header_bits.checked_add(payload_bits).ok_or(StenError::BadPayload)?;
+        if total_bits > container.len() {
+            return Err(StenError::BadPayload);
+        }
+
+        Ok(StenReader {
+            container,
+            cursor_bits: header_bits,
+            payload_remaining: framed_len - TRAILER_LEN,
+            crc: 0,
+            buf: Vec::new(),
+            buf_pos: 0,
+            trailer_checked: false,
+        })
+    }
+
+    fn pull(&mut self, count: usize) -> Vec<u8> {
+        let bytes = extract_bits(self.container, self.cursor_bits, count);
+        self.cursor_bits += count * 8;
+        bytes
+    }
+
+    /// Read and verify the trailing tag + CRC-32 digest once the whole
+    /// payload has been streamed out.
+    fn check_trailer(&mut self) -> io::Result<()> {
+        if self.trailer_checked {
+            return Ok(());
+        }
+        self.trailer_checked = true;
+
+        let trailer = self.pull(TRAILER_LEN);
+        let (&tag, digest) = trailer.split_first().expect("TRAILER_LEN is non-zero");
+        if tag != Crc32::TAG {
+            return Err(io_err(StenError::BadPayload));
+        }
+        if !secure_cmp(digest, &self.crc.to_le_bytes()) {
+            return Err(io_err(StenError::FailedChecksum));
+        }
+        Ok(())
+    }
+}
+
+impl Read for StenReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.buf_pos == self.buf.len() {
+            self.buf.clear();
+            self.buf_pos = 0;
+
+            if self.payload_remaining == 0 {
+                self.check_trailer()?;
+                return Ok(0);
+            }
+
+            let take = self.payload_remaining.min(STREAM_BLOCK_SIZE);
+            let chunk = self.pull(take);
+            self.crc = get_crc(self.crc, &chunk);
+            self.payload_remaining -= take;
+            self.buf = chunk;
+        }
+
+        let available = &self.buf[self.buf_pos..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.buf_pos += n;
+        Ok(n)
+    }
+}
+
+/// Incrementally writes a CRC-32-checksummed stego frame into a container
+/// through [`Write`], folding the running checksum as payload bytes arrive
+/// instead of buffering the whole payload up front.
+///
+/// The payload length must be known ahead of time, since the varint header
+/// is bit-embedded before any payload bytes are: construct with
+/// [`StenWriter::new`], write exactly `payload_len` bytes, then call
+/// [`StenWriter::finish`] to embed the trailing tag and digest.
+pub struct StenWriter<'a, C: Container> {
+    container: &'a mut C,
+    cursor_bits: usize,
+    payload_len: usize,
+    payload_written: usize,
+    crc: u32,
+}
+
+impl<'a, C: Container> StenWriter<'a, C> {
+    /// Embed the varint header for a `payload_len`-byte payload into
+    /// `container` and prepare to stream the payload itself.
+    pub fn new(container: &'a mut C, payload_len: usize) -> Result<Self, StenError> {
+        let framed_len = payload_len + TRAILER_LEN;
+        let header = encode_varint(framed_len as u64);
+        let header_bits = header.len() * 8;
+
+        let max_size = container.as_bytes().len() / 8;
+        if framed_len > max_size {
+            return Err(StenError::PayloadTooLarge(framed_len, max_size));
+        }
+
+        embed_bits(container.as_mut_bytes(), 0, &header);
+
+        Ok(StenWriter {
+            container,
+            cursor_bits: header_bits,
+            payload_len,
+            payload_written: 0,
+            crc: 0,
+        })
+    }
+
+    /// Embed the trailing tag and CRC-32 digest, completing the frame.
+    /// Fails if fewer than `payload_len` bytes were written.
+    pub fn finish(self) -> Result<(), StenError> {
+        if self.payload_written != self.payload_len {
+            return Err(StenError::BadPayload);
+        }
+
+        let mut trailer = vec![Crc32::TAG];
+        trailer.extend_from_slice(&self.crc.to_le_bytes());
+        embed_bits(self.container.as_mut_bytes(), self.cursor_bits, &trailer);
+        Ok(())
+    }
+}
+
+impl<C: Container> Write for StenWriter<'_, C> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let remaining = self.payload_len - self.payload_written;
+        let take = remaining.min(buf.len());
+        if take == 0 {
+            return Ok(0);
+        }
+
+        embed_bits(self.container.as_mut_bytes(), self.cursor_bits, &buf[..take]);
+        self.crc = get_crc(self.crc, &buf[..take]);
+        self.cursor_bits += take * 8;
+        self.payload_written += take;
+        Ok(take)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sten_writer_reader_roundtrip() {
+        // Payload is deliberately larger than `STREAM_BLOCK_SIZE`, so the
+        // roundtrip actually exercises multiple internal-buffer refills. The
+        // container is sized generously (+10 bytes of header/trailer slack)
+        // rather than exactly, since the varint header itself grows past one
+        // byte once the framed length exceeds 127.
+        let payload = b"the quick brown fox jumps over the lazy dog".repeat(200);
+        let mut container = vec![13u8; (payload.len() + TRAILER_LEN + 10) * 8];
+
+        let mut writer = StenWriter::new(&mut container, payload.len()).unwrap();
+        writer.write_all(&payload).unwrap();
+        writer.finish().unwrap();
+
+        let mut reader = StenReader::new(&container).unwrap();
+        let mut decoded = Vec::new();
+        reader.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn test_sten_reader_rejects_huge_framed_len_without_overflowing() {
+        // A header claiming a near-u64::MAX framed length must be rejected as
+        // malformed rather than overflowing the `* 8` bit-count conversion.
+        let header = encode_varint(u64::MAX);
+        let mut container = vec![0u8; header.len() * 8];
+        for (byte_idx, byte) in header.iter().enumerate() {
+            for bit_pos in 0..8 {
+                container[byte_idx * 8 + bit_pos] = (byte >> bit_pos) & 1;
+            }
+        }
+
+        assert!(matches!(StenReader::new(&container), Err(StenError::BadPayload)));
+    }
+
+    #[test]
+    fn test_sten_reader_rejects_corrupted_payload() {
+        let mut container = vec![13u8; 4096];
+        let payload = b"streamed payload".to_vec();
+
+        let mut writer = StenWriter::new(&mut container, payload.len()).unwrap();
+        writer.write_all(&payload).unwrap();
+        writer.finish().unwrap();
+
+        container[8] ^= 1; // flip the first payload bit
+
+        let mut reader = StenReader::new(&container).unwrap();
+        let mut decoded = Vec::new();
+        let err = reader.read_to_end(&mut decoded).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}